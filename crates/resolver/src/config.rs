@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+use crate::resolver::ResolverType;
+
+/// How a package's version is managed by semifold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionMode {
+    /// The resolver decides the next version from semver bump rules.
+    Semantic,
+    /// The package is versioned independently of semifold and is only read,
+    /// never bumped.
+    Fixed,
+}
+
+/// A user-configured command run around publishing, e.g. `go build` before
+/// a release or `gh release create` after tagging.
+#[derive(Debug, Clone)]
+pub struct CommandSpec {
+    pub command: String,
+    pub args: Option<Vec<String>>,
+    /// When `Some(true)`, this command still runs during a dry run.
+    pub dry_run: Option<bool>,
+}
+
+/// A single workspace package's resolver configuration.
+#[derive(Debug, Clone)]
+pub struct PackageConfig {
+    pub path: PathBuf,
+    pub resolver: ResolverType,
+    pub version_mode: VersionMode,
+    /// Extra files or directories to include alongside the package when
+    /// publishing, beyond what the resolver discovers on its own.
+    pub assets: Vec<String>,
+}
+
+/// Per-resolver-backend publish behavior, shared by every package that
+/// backend resolves.
+#[derive(Debug, Clone, Default)]
+pub struct ResolverConfig {
+    /// Create annotated (`git tag -a`) rather than lightweight tags.
+    pub tag_annotated: bool,
+    /// Tag immediately on bump rather than deferring to `publish`.
+    pub tag_at_bump: bool,
+    pub prepublish: Vec<CommandSpec>,
+    pub publish: Vec<CommandSpec>,
+}
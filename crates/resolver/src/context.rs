@@ -0,0 +1,8 @@
+/// Cross-cutting options for a single command invocation, passed to
+/// `Resolver::bump`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Context {
+    /// When set, resolvers log what they would do instead of touching disk
+    /// or git state.
+    pub dry_run: bool,
+}
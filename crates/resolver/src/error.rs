@@ -0,0 +1,26 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+/// Errors that can occur while resolving, bumping, sorting, or publishing
+/// packages through any `Resolver` backend.
+#[derive(Debug, Error)]
+pub enum ResolveError {
+    #[error("{path}: {reason}")]
+    ParseError { path: PathBuf, reason: String },
+
+    #[error("file or directory not found: {path}")]
+    FileOrDirNotFound { path: PathBuf },
+
+    #[error("dependency cycle detected among modules: {}", modules.join(", "))]
+    DependencyCycle { modules: Vec<String> },
+
+    #[error("command failed: {command}\n{stderr}")]
+    CommandFailed { command: String, stderr: String },
+
+    #[error("invalid version: {0}")]
+    InvalidVersion(#[from] semver::Error),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
@@ -0,0 +1,6 @@
+pub mod config;
+pub mod context;
+pub mod error;
+pub mod lockfile;
+pub mod resolver;
+mod utils;
@@ -0,0 +1,310 @@
+use std::{collections::HashMap, path::Path};
+
+use crate::error::ResolveError;
+
+/// Name of the lockfile written to the workspace root.
+pub const LOCKFILE_NAME: &str = "semifold.lock";
+
+/// Where a package's resolved version was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionSource {
+    VersionFile,
+    GitTag,
+    Default,
+}
+
+impl VersionSource {
+    fn as_str(self) -> &'static str {
+        match self {
+            VersionSource::VersionFile => "version-file",
+            VersionSource::GitTag => "git-tag",
+            VersionSource::Default => "default",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "version-file" => Some(VersionSource::VersionFile),
+            "git-tag" => Some(VersionSource::GitTag),
+            "default" => Some(VersionSource::Default),
+            _ => None,
+        }
+    }
+}
+
+/// One resolved package's entry in the workspace lockfile: its version,
+/// where that version came from, and the intra-workspace packages it
+/// depends on.
+#[derive(Debug, Clone)]
+pub struct LockEntry {
+    pub name: String,
+    pub module: Option<String>,
+    pub version: String,
+    pub source: VersionSource,
+    pub depends_on: Vec<String>,
+}
+
+/// The full set of resolved packages for a workspace, as written to
+/// `semifold.lock`. Analogous to `Cargo.lock`, but resolver-agnostic: any
+/// `Resolver` backend can contribute entries.
+#[derive(Debug, Clone, Default)]
+pub struct Lockfile {
+    pub packages: Vec<LockEntry>,
+}
+
+/// Why a package's resolved version differs from the previous lockfile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockChangeReason {
+    Added,
+    VersionBumped,
+    DependencyBumped,
+}
+
+#[derive(Debug, Clone)]
+pub struct LockChange {
+    pub name: String,
+    pub old_version: Option<String>,
+    pub new_version: String,
+    pub reason: LockChangeReason,
+}
+
+impl Lockfile {
+    pub fn new(packages: Vec<LockEntry>) -> Self {
+        Self { packages }
+    }
+
+    /// Write this lockfile to `<root>/semifold.lock`.
+    pub fn write(&self, root: &Path) -> Result<(), ResolveError> {
+        std::fs::write(root.join(LOCKFILE_NAME), self.serialize())?;
+        Ok(())
+    }
+
+    /// Read the lockfile at `<root>/semifold.lock`, if one exists.
+    pub fn read(root: &Path) -> Result<Option<Self>, ResolveError> {
+        let path = root.join(LOCKFILE_NAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        Ok(Some(Self::deserialize(&content, &path)?))
+    }
+
+    fn serialize(&self) -> String {
+        let mut out = String::from("# This file is @generated by semifold. Do not edit by hand.\n");
+
+        for entry in &self.packages {
+            out.push_str("\n[[package]]\n");
+            out.push_str(&format!("name = \"{}\"\n", entry.name));
+            if let Some(module) = &entry.module {
+                out.push_str(&format!("module = \"{}\"\n", module));
+            }
+            out.push_str(&format!("version = \"{}\"\n", entry.version));
+            out.push_str(&format!("source = \"{}\"\n", entry.source.as_str()));
+            if !entry.depends_on.is_empty() {
+                out.push_str("dependencies = [\n");
+                for dep in &entry.depends_on {
+                    out.push_str(&format!("    \"{}\",\n", dep));
+                }
+                out.push_str("]\n");
+            }
+        }
+
+        out
+    }
+
+    fn deserialize(content: &str, path: &Path) -> Result<Self, ResolveError> {
+        let mut packages = Vec::new();
+        let mut current: Option<LockEntry> = None;
+        let mut in_dependencies = false;
+
+        for raw_line in content.lines() {
+            let line = raw_line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line == "[[package]]" {
+                if let Some(entry) = current.take() {
+                    packages.push(entry);
+                }
+                current = Some(LockEntry {
+                    name: String::new(),
+                    module: None,
+                    version: String::new(),
+                    source: VersionSource::Default,
+                    depends_on: Vec::new(),
+                });
+                in_dependencies = false;
+                continue;
+            }
+
+            let Some(entry) = current.as_mut() else {
+                continue;
+            };
+
+            if in_dependencies {
+                if line == "]" {
+                    in_dependencies = false;
+                } else if let Some(dep) = line
+                    .trim_end_matches(',')
+                    .trim()
+                    .strip_prefix('"')
+                    .and_then(|s| s.strip_suffix('"'))
+                {
+                    entry.depends_on.push(dep.to_string());
+                }
+                continue;
+            }
+
+            if line == "dependencies = [" {
+                in_dependencies = true;
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let key = key.trim();
+            let value = value.trim().trim_matches('"');
+
+            match key {
+                "name" => entry.name = value.to_string(),
+                "module" => entry.module = Some(value.to_string()),
+                "version" => entry.version = value.to_string(),
+                "source" => {
+                    entry.source = VersionSource::parse(value).ok_or_else(|| {
+                        ResolveError::ParseError {
+                            path: path.to_path_buf(),
+                            reason: format!("unknown lockfile version source: {}", value),
+                        }
+                    })?;
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(entry) = current.take() {
+            packages.push(entry);
+        }
+
+        Ok(Self { packages })
+    }
+
+    /// Diff this (freshly resolved) lockfile against the previous one,
+    /// reporting which packages changed version and a best-effort reason:
+    /// bumped directly, or because one of its workspace dependencies was.
+    pub fn diff(&self, previous: &Lockfile) -> Vec<LockChange> {
+        let previous_versions: HashMap<&str, &str> = previous
+            .packages
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.version.as_str()))
+            .collect();
+
+        let version_changed: HashMap<&str, bool> = self
+            .packages
+            .iter()
+            .map(|entry| {
+                let changed = match previous_versions.get(entry.name.as_str()) {
+                    None => true,
+                    Some(old_version) => *old_version != entry.version,
+                };
+                (entry.name.as_str(), changed)
+            })
+            .collect();
+
+        self.packages
+            .iter()
+            .filter(|entry| version_changed[entry.name.as_str()])
+            .map(|entry| {
+                let old_version = previous_versions
+                    .get(entry.name.as_str())
+                    .map(|v| v.to_string());
+
+                let reason = if old_version.is_none() {
+                    LockChangeReason::Added
+                } else if entry
+                    .depends_on
+                    .iter()
+                    .any(|dep| version_changed.get(dep.as_str()).copied().unwrap_or(false))
+                {
+                    LockChangeReason::DependencyBumped
+                } else {
+                    LockChangeReason::VersionBumped
+                };
+
+                LockChange {
+                    name: entry.name.clone(),
+                    old_version,
+                    new_version: entry.version.clone(),
+                    reason,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, version: &str, source: VersionSource, depends_on: &[&str]) -> LockEntry {
+        LockEntry {
+            name: name.to_string(),
+            module: Some(format!("example.com/{}", name)),
+            version: version.to_string(),
+            source,
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn round_trips_through_serialize_and_deserialize() {
+        let lockfile = Lockfile::new(vec![
+            entry("foo", "1.2.3", VersionSource::GitTag, &["bar"]),
+            entry("bar", "0.1.0", VersionSource::Default, &[]),
+        ]);
+
+        let serialized = lockfile.serialize();
+        let parsed = Lockfile::deserialize(&serialized, Path::new("semifold.lock")).unwrap();
+
+        assert_eq!(parsed.packages.len(), 2);
+
+        assert_eq!(parsed.packages[0].name, "foo");
+        assert_eq!(
+            parsed.packages[0].module.as_deref(),
+            Some("example.com/foo")
+        );
+        assert_eq!(parsed.packages[0].version, "1.2.3");
+        assert_eq!(parsed.packages[0].source, VersionSource::GitTag);
+        assert_eq!(parsed.packages[0].depends_on, vec!["bar".to_string()]);
+
+        assert_eq!(parsed.packages[1].name, "bar");
+        assert_eq!(parsed.packages[1].source, VersionSource::Default);
+        assert!(parsed.packages[1].depends_on.is_empty());
+    }
+
+    #[test]
+    fn diff_distinguishes_direct_from_dependency_bumps() {
+        let previous = Lockfile::new(vec![
+            entry("foo", "1.0.0", VersionSource::Default, &["bar"]),
+            entry("bar", "1.0.0", VersionSource::Default, &[]),
+            entry("baz", "1.0.0", VersionSource::Default, &[]),
+        ]);
+        let current = Lockfile::new(vec![
+            entry("foo", "1.0.1", VersionSource::Default, &["bar"]),
+            entry("bar", "1.1.0", VersionSource::Default, &[]),
+            entry("baz", "1.0.0", VersionSource::Default, &[]),
+        ]);
+
+        let mut changes = current.diff(&previous);
+        changes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].name, "bar");
+        assert_eq!(changes[0].reason, LockChangeReason::VersionBumped);
+        assert_eq!(changes[1].name, "foo");
+        assert_eq!(changes[1].reason, LockChangeReason::DependencyBumped);
+    }
+}
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, path::Path, process::Command};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 use regex::Regex;
 
@@ -6,6 +10,7 @@ use crate::{
     config::{PackageConfig, ResolverConfig, VersionMode},
     context,
     error::ResolveError,
+    lockfile::{self, LockEntry, VersionSource},
     resolver::{ResolvedPackage, Resolver, ResolverType},
     utils,
 };
@@ -17,6 +22,7 @@ struct GoMod {
     pub module: String,
     pub go_version: Option<String>,
     pub require: Vec<GoRequire>,
+    pub replace: Vec<GoReplace>,
 }
 
 #[derive(Debug)]
@@ -26,6 +32,23 @@ struct GoRequire {
     pub version: String,
 }
 
+/// A `replace` directive, pointing `old_path` at either a local directory
+/// (e.g. `../local`) or a forked module path, optionally with a version.
+#[derive(Debug)]
+#[allow(dead_code)]
+struct GoReplace {
+    pub old_path: String,
+    pub new_path: String,
+}
+
+impl GoReplace {
+    /// A `replace` target is a local directory (rather than another module)
+    /// when it's a relative or absolute filesystem path.
+    fn is_local(&self) -> bool {
+        self.new_path.starts_with('.') || self.new_path.starts_with('/')
+    }
+}
+
 /// Represents a go.work file for Go workspaces
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -34,6 +57,11 @@ struct GoWork {
     pub use_dirs: Vec<String>,
 }
 
+/// The parsed `GoMod` for each workspace package, keyed by package name, and
+/// a map of dependency package name -> the names of packages that depend on
+/// it. Returned by `GoResolver::dependency_graph`.
+type DependencyGraph = (HashMap<String, GoMod>, HashMap<String, Vec<String>>);
+
 pub struct GoResolver;
 
 impl GoResolver {
@@ -42,6 +70,7 @@ impl GoResolver {
         let mut module = String::new();
         let mut go_version = None;
         let mut require = Vec::new();
+        let mut replace = Vec::new();
 
         let module_re = Regex::new(r"^module\s+(.+)$").map_err(|e| ResolveError::ParseError {
             path: path.to_path_buf(),
@@ -65,7 +94,21 @@ impl GoResolver {
                 reason: format!("Invalid regex: {}", e),
             })?;
 
+        let replace_single_re = Regex::new(r"^replace\s+(\S+)(?:\s+\S+)?\s+=>\s+(\S+)(?:\s+\S+)?$")
+            .map_err(|e| ResolveError::ParseError {
+                path: path.to_path_buf(),
+                reason: format!("Invalid regex: {}", e),
+            })?;
+
+        let replace_line_re = Regex::new(r"^(\S+)(?:\s+\S+)?\s+=>\s+(\S+)(?:\s+\S+)?$").map_err(
+            |e| ResolveError::ParseError {
+                path: path.to_path_buf(),
+                reason: format!("Invalid regex: {}", e),
+            },
+        )?;
+
         let mut in_require_block = false;
+        let mut in_replace_block = false;
 
         for line in content.lines() {
             let line = line.trim();
@@ -99,6 +142,18 @@ impl GoResolver {
                 continue;
             }
 
+            // Parse replace block start
+            if line == "replace (" {
+                in_replace_block = true;
+                continue;
+            }
+
+            // Parse replace block end
+            if line == ")" && in_replace_block {
+                in_replace_block = false;
+                continue;
+            }
+
             // Parse single-line require
             if let Some(caps) = require_single_re.captures(line) {
                 require.push(GoRequire {
@@ -108,6 +163,15 @@ impl GoResolver {
                 continue;
             }
 
+            // Parse single-line replace
+            if let Some(caps) = replace_single_re.captures(line) {
+                replace.push(GoReplace {
+                    old_path: caps[1].to_string(),
+                    new_path: caps[2].to_string(),
+                });
+                continue;
+            }
+
             // Parse require block entries
             if in_require_block {
                 if let Some(caps) = require_line_re.captures(line) {
@@ -121,6 +185,17 @@ impl GoResolver {
                         });
                     }
                 }
+                continue;
+            }
+
+            // Parse replace block entries
+            if in_replace_block {
+                if let Some(caps) = replace_line_re.captures(line) {
+                    replace.push(GoReplace {
+                        old_path: caps[1].to_string(),
+                        new_path: caps[2].to_string(),
+                    });
+                }
             }
         }
 
@@ -135,6 +210,7 @@ impl GoResolver {
             module,
             go_version,
             require,
+            replace,
         })
     }
 
@@ -271,20 +347,32 @@ impl GoResolver {
 
             // Check if tag matches module prefix for submodules
             if let Some(stripped) = tag.strip_prefix(&format!("{}/", module_path)) {
-                if let Some(caps) = version_re.captures(stripped) {
-                    return Ok(Some(caps[1].to_string()));
+                if let Some(version) = Self::validated_tag_version(&version_re, stripped) {
+                    return Ok(Some(version));
                 }
             }
 
             // Check for root module version tags
-            if let Some(caps) = version_re.captures(tag) {
-                return Ok(Some(caps[1].to_string()));
+            if let Some(version) = Self::validated_tag_version(&version_re, tag) {
+                return Ok(Some(version));
             }
         }
 
         Ok(None)
     }
 
+    /// Match `text` against the tag version shape, then require the
+    /// candidate to also be a valid (possibly pseudo-) semver version —
+    /// e.g. `0.0.0-20191109021931-daa7c04131f5` — rather than trusting
+    /// `version_re`'s permissive pre-release character class alone, which
+    /// would also accept strings that merely look like a version.
+    fn validated_tag_version(version_re: &Regex, text: &str) -> Option<String> {
+        let caps = version_re.captures(text)?;
+        let candidate = caps[1].to_string();
+        semver::Version::parse(Self::normalize_go_version(&candidate)).ok()?;
+        Some(candidate)
+    }
+
     /// Get version for a Go module using priority: custom file > git tag > version.go > default
     fn get_version(
         &self,
@@ -336,9 +424,11 @@ const Version = "{}"
 
         let content = std::fs::read_to_string(&version_go_path)?;
 
-        // Replace version in existing file
+        // Replace version in existing file. The build-metadata group is
+        // captured separately so it can be preserved below if `new_version`
+        // doesn't specify its own.
         let version_re = Regex::new(
-            r#"(?i)((?:const|var)\s+version\s*=\s*")v?[\d]+\.[\d]+\.[\d]+(?:-[a-zA-Z0-9.-]+)?(?:\+[a-zA-Z0-9.-]+)?(")"#,
+            r#"(?i)((?:const|var)\s+version\s*=\s*")v?[\d]+\.[\d]+\.[\d]+(?:-[a-zA-Z0-9.-]+)?(\+[a-zA-Z0-9.-]+)?(")"#,
         )
         .map_err(|e| ResolveError::ParseError {
             path: version_go_path.clone(),
@@ -346,7 +436,14 @@ const Version = "{}"
         })?;
 
         let updated_content = version_re.replace(&content, |caps: &regex::Captures| {
-            format!("{}{}{}", &caps[1], new_version, &caps[2])
+            // Preserve the existing build metadata (e.g. `+company.1`) on
+            // round-trip unless `new_version` already carries its own.
+            let build_metadata = if new_version.contains('+') {
+                ""
+            } else {
+                caps.get(2).map_or("", |m| m.as_str())
+            };
+            format!("{}{}{}{}", &caps[1], new_version, build_metadata, &caps[3])
         });
 
         std::fs::write(&version_go_path, updated_content.as_ref())?;
@@ -363,38 +460,301 @@ const Version = "{}"
             .unwrap_or(module_path)
             .to_string()
     }
-}
 
-impl Resolver for GoResolver {
-    fn resolve(
-        &mut self,
+    /// Lexically collapse `.` and `..` components without touching the
+    /// filesystem, so `replace` targets can be compared against workspace
+    /// package paths even when they don't exist on disk (e.g. in tests).
+    fn normalize_path(path: &Path) -> PathBuf {
+        let mut stack: Vec<std::path::Component> = Vec::new();
+        for component in path.components() {
+            match component {
+                std::path::Component::CurDir => {}
+                std::path::Component::ParentDir => {
+                    if matches!(stack.last(), Some(std::path::Component::Normal(_))) {
+                        stack.pop();
+                    } else {
+                        stack.push(component);
+                    }
+                }
+                other => stack.push(other),
+            }
+        }
+        stack.iter().collect()
+    }
+
+    /// Resolve a `require` path to the workspace package name it depends on,
+    /// if any. A `replace` directive takes priority over the raw `require`,
+    /// matching Go's own resolution: if `req_path` is replaced, the build
+    /// depends on the replacement (a workspace-local directory, or nothing
+    /// this function tracks if it points at another module), never on
+    /// whatever `req_path` would otherwise resolve to.
+    fn resolve_dependency_name(
+        req_path: &str,
+        go_mod: &GoMod,
+        package_dir: &Path,
         root: &Path,
-        pkg_config: &PackageConfig,
-    ) -> Result<ResolvedPackage, ResolveError> {
-        let go_mod_path = root.join(&pkg_config.path).join("go.mod");
-        if !go_mod_path.exists() {
-            return Err(ResolveError::FileOrDirNotFound {
-                path: go_mod_path.clone(),
-            });
+        module_to_name: &HashMap<String, String>,
+        path_to_name: &HashMap<PathBuf, String>,
+    ) -> Option<String> {
+        if let Some(replacement) = go_mod.replace.iter().find(|r| r.old_path == req_path) {
+            if !replacement.is_local() {
+                return None;
+            }
+
+            let resolved_dir =
+                Self::normalize_path(&root.join(package_dir).join(&replacement.new_path));
+            return path_to_name.get(&resolved_dir).cloned();
         }
 
-        let go_mod_str = std::fs::read_to_string(&go_mod_path)?;
-        let go_mod = Self::parse_go_mod(&go_mod_str, &go_mod_path)?;
+        module_to_name.get(req_path).cloned()
+    }
 
-        let version_str = self.get_version(root, &pkg_config.path, &go_mod.module)?;
-        let version = semver::Version::parse(&version_str)?;
+    /// Parse every workspace package's go.mod and build the intra-workspace
+    /// dependency edges, honoring `replace` directives. Returns the parsed
+    /// `GoMod`s by package name, and a map of dependency name -> the names
+    /// of packages that depend on it. Shared by `sort_packages` (for the
+    /// topological sort) and `lock_entries` (for the lockfile's graph).
+    fn dependency_graph(
+        root: &Path,
+        entries: &[(String, PathBuf)],
+    ) -> Result<DependencyGraph, ResolveError> {
+        let cached_packages = entries
+            .iter()
+            .try_fold(HashMap::new(), |mut acc, (name, path)| {
+                let go_mod_path = root.join(path).join("go.mod");
+                let go_mod_str = std::fs::read_to_string(&go_mod_path)?;
+                let go_mod = Self::parse_go_mod(&go_mod_str, &go_mod_path)?;
+                acc.insert(name.clone(), go_mod);
+                Ok::<_, ResolveError>(acc)
+            })?;
 
-        let package = ResolvedPackage {
-            name: Self::module_name_from_path(&go_mod.module),
-            version,
-            path: pkg_config.path.clone(),
-            private: false,
+        let module_to_name: HashMap<String, String> = cached_packages
+            .iter()
+            .map(|(name, go_mod)| (go_mod.module.clone(), name.clone()))
+            .collect();
+
+        // A map of workspace directory -> package name, so a `replace`
+        // directive pointing at a local directory can be resolved back to
+        // the workspace package it targets.
+        let path_to_name: HashMap<PathBuf, String> = entries
+            .iter()
+            .map(|(name, path)| (Self::normalize_path(&root.join(path)), name.clone()))
+            .collect();
+
+        let mut dependents: HashMap<String, Vec<String>> = HashMap::new();
+
+        for (name, path) in entries {
+            let go_mod = cached_packages.get(name).unwrap();
+            for req in &go_mod.require {
+                let dep_name = Self::resolve_dependency_name(
+                    &req.path,
+                    go_mod,
+                    path,
+                    root,
+                    &module_to_name,
+                    &path_to_name,
+                );
+
+                if let Some(dep_name) = dep_name {
+                    if &dep_name != name {
+                        dependents.entry(dep_name).or_default().push(name.clone());
+                    }
+                }
+            }
+        }
+
+        Ok((cached_packages, dependents))
+    }
+
+    /// Where a package's resolved version came from, using the same
+    /// priority order as `get_version`.
+    fn version_source(
+        &self,
+        root: &Path,
+        package_path: &Path,
+        module_path: &str,
+    ) -> Result<VersionSource, ResolveError> {
+        let full_path = root.join(package_path);
+
+        if self
+            .extract_version_from_version_go(&full_path)?
+            .is_some()
+        {
+            return Ok(VersionSource::VersionFile);
+        }
+
+        if self
+            .extract_version_from_git_tag(root, module_path)?
+            .is_some()
+        {
+            return Ok(VersionSource::GitTag);
+        }
+
+        Ok(VersionSource::Default)
+    }
+
+    /// Rewrite the version token of every `require` line for `module_path`,
+    /// leaving indentation, the `require` keyword and any trailing
+    /// `// indirect` comment untouched. Returns the new content and whether
+    /// anything was changed.
+    fn rewrite_require_version(content: &str, module_path: &str, new_version: &str) -> (String, bool) {
+        let require_re = Regex::new(&format!(
+            r"(?m)^(?P<prefix>\s*(?:require\s+)?{}\s+)(?P<version>\S+)(?P<rest>[^\n]*)",
+            regex::escape(module_path)
+        ))
+        .expect("valid regex");
+
+        let normalized_version = if new_version.starts_with('v') {
+            new_version.to_string()
+        } else {
+            format!("v{}", new_version)
         };
+        let new_semver = semver::Version::parse(Self::normalize_go_version(new_version)).ok();
 
-        Ok(package)
+        let mut changed = false;
+        let updated = require_re.replace_all(content, |caps: &regex::Captures| {
+            let old_version = &caps["version"];
+
+            // Entries of a `replace` block share the `path version` shape
+            // but carry `=>`; they are not requirements, so leave them as is.
+            if old_version == "=>" || caps["rest"].contains("=>") {
+                return caps[0].to_string();
+            }
+
+            // Pseudo-versions (e.g. `v0.0.0-20191109021931-daa7c04131f5`) are
+            // themselves valid semver pre-releases, so this comparison
+            // orders them against real releases using normal pre-release
+            // precedence. Skip the rewrite if the existing requirement is
+            // already at or ahead of the bumped version (e.g. it's already
+            // pinned to a newer fork) rather than silently downgrading it.
+            let old_semver = semver::Version::parse(Self::normalize_go_version(old_version)).ok();
+            let should_replace = match (&old_semver, &new_semver) {
+                (Some(old), Some(new)) => old < new,
+                _ => true,
+            };
+
+            if !should_replace {
+                return caps[0].to_string();
+            }
+
+            changed = true;
+            format!("{}{}{}", &caps["prefix"], normalized_version, &caps["rest"])
+        });
+
+        (updated.into_owned(), changed)
     }
 
-    fn resolve_all(&mut self, root: &Path) -> Result<Vec<ResolvedPackage>, ResolveError> {
+    /// Strip the leading `v` Go conventionally prefixes versions with, so
+    /// the remainder can be handed to `semver::Version::parse`.
+    fn normalize_go_version(raw: &str) -> &str {
+        raw.strip_prefix('v').unwrap_or(raw)
+    }
+
+    /// After bumping `bumped_module` to `new_version`, rewrite the matching
+    /// `require` entry in every other workspace package's go.mod so their
+    /// dependency requirements stay in sync with the new version. `entries`
+    /// is the same (name, path) workspace membership list `sort_packages`
+    /// and `lock_entries` build their dependency graphs from, rather than a
+    /// narrower rediscovery from go.work — so propagation also reaches
+    /// packages enumerated through semifold's own config.
+    fn propagate_bump(
+        &self,
+        ctx: &context::Context,
+        root: &Path,
+        bumped_package_name: &str,
+        bumped_module: &str,
+        new_version: &str,
+        entries: &[(String, PathBuf)],
+    ) -> Result<(), ResolveError> {
+        for (name, path) in entries {
+            if name == bumped_package_name {
+                continue;
+            }
+
+            let go_mod_path = root.join(path).join("go.mod");
+            if !go_mod_path.exists() {
+                continue;
+            }
+
+            let content = std::fs::read_to_string(&go_mod_path)?;
+            let (updated, changed) =
+                Self::rewrite_require_version(&content, bumped_module, new_version);
+
+            if !changed {
+                continue;
+            }
+
+            if ctx.dry_run {
+                log::warn!(
+                    "Skip updating require {} to v{} in {:?} due to dry run",
+                    bumped_module,
+                    new_version,
+                    go_mod_path
+                );
+                continue;
+            }
+
+            std::fs::write(&go_mod_path, updated)?;
+            log::info!(
+                "Updated require {} to v{} in {:?}",
+                bumped_module,
+                new_version,
+                go_mod_path
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Create the git tag for a package's newly resolved version, using the
+    /// submodule-prefixed form `<module-path>/vX.Y.Z` when the package lives
+    /// in a subdirectory of the repo and plain `vX.Y.Z` for the root module
+    /// — the same two shapes `extract_version_from_git_tag` parses. The
+    /// prefix must be built from `module_path` (the Go import path), not
+    /// `package_path` (the on-disk directory): `extract_version_from_git_tag`
+    /// strips `"{module_path}/"`, and the two only coincide when a
+    /// submodule's import path happens to match its bare directory name.
+    fn create_git_tag(
+        &self,
+        root: &Path,
+        package_path: &Path,
+        module_path: &str,
+        new_version: &str,
+        resolver_config: &ResolverConfig,
+    ) -> Result<(), ResolveError> {
+        let tag_name = if package_path.as_os_str() == "." {
+            format!("v{}", new_version)
+        } else {
+            format!("{}/v{}", module_path, new_version)
+        };
+
+        let mut args = vec!["tag".to_string()];
+        if resolver_config.tag_annotated {
+            args.push("-a".to_string());
+            args.push(tag_name.clone());
+            args.push("-m".to_string());
+            args.push(tag_name.clone());
+        } else {
+            args.push(tag_name.clone());
+        }
+
+        let output = Command::new("git").args(&args).current_dir(root).output()?;
+
+        if !output.status.success() {
+            return Err(ResolveError::CommandFailed {
+                command: format!("git {}", args.join(" ")),
+                stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            });
+        }
+
+        log::info!("Created git tag {}", tag_name);
+
+        Ok(())
+    }
+
+    /// The actual resolution logic behind the `resolve_all` trait method,
+    /// split out so `resolve_all` can wrap it with the lockfile write below.
+    fn resolve_all_packages(&mut self, root: &Path) -> Result<Vec<ResolvedPackage>, ResolveError> {
         // First check for go.work (Go workspace)
         let go_work_path = root.join("go.work");
         if go_work_path.exists() {
@@ -448,27 +808,177 @@ impl Resolver for GoResolver {
         Ok(vec![package])
     }
 
+    /// After resolving the workspace, build this resolver's lockfile
+    /// entries, diff them against any previous `semifold.lock` (logging
+    /// what changed and, best-effort, why), and write the refreshed
+    /// lockfile back to the repo root.
+    fn write_lockfile(&mut self, root: &Path, packages: &[ResolvedPackage]) -> Result<(), ResolveError> {
+        if packages.is_empty() {
+            return Ok(());
+        }
+
+        let mut entries = self.lock_entries(root, packages)?;
+        let previous = lockfile::Lockfile::read(root)?;
+
+        // `entries` only covers the packages this resolver just resolved.
+        // Carry forward every other resolver backend's entries from the
+        // previous lockfile untouched, so writing this backend's results
+        // doesn't clobber theirs.
+        if let Some(previous) = &previous {
+            let resolved_names: HashSet<String> =
+                entries.iter().map(|entry| entry.name.clone()).collect();
+            entries.extend(
+                previous
+                    .packages
+                    .iter()
+                    .filter(|entry| !resolved_names.contains(&entry.name))
+                    .cloned(),
+            );
+        }
+
+        let current = lockfile::Lockfile::new(entries);
+
+        if let Some(previous) = &previous {
+            for change in current.diff(previous) {
+                let old_version = change.old_version.as_deref().unwrap_or("none");
+                match change.reason {
+                    lockfile::LockChangeReason::Added => {
+                        log::info!("{} added at version {}", change.name, change.new_version);
+                    }
+                    lockfile::LockChangeReason::VersionBumped => {
+                        log::info!(
+                            "{} changed {} -> {}",
+                            change.name,
+                            old_version,
+                            change.new_version
+                        );
+                    }
+                    lockfile::LockChangeReason::DependencyBumped => {
+                        log::info!(
+                            "{} changed {} -> {} because a dependency was bumped",
+                            change.name,
+                            old_version,
+                            change.new_version
+                        );
+                    }
+                }
+            }
+        }
+
+        current.write(root)
+    }
+}
+
+impl Resolver for GoResolver {
+    fn resolve(
+        &mut self,
+        root: &Path,
+        pkg_config: &PackageConfig,
+    ) -> Result<ResolvedPackage, ResolveError> {
+        let go_mod_path = root.join(&pkg_config.path).join("go.mod");
+        if !go_mod_path.exists() {
+            return Err(ResolveError::FileOrDirNotFound {
+                path: go_mod_path.clone(),
+            });
+        }
+
+        let go_mod_str = std::fs::read_to_string(&go_mod_path)?;
+        let go_mod = Self::parse_go_mod(&go_mod_str, &go_mod_path)?;
+
+        let version_str = self.get_version(root, &pkg_config.path, &go_mod.module)?;
+        let version = semver::Version::parse(&version_str)?;
+
+        let package = ResolvedPackage {
+            name: Self::module_name_from_path(&go_mod.module),
+            version,
+            path: pkg_config.path.clone(),
+            private: false,
+        };
+
+        Ok(package)
+    }
+
+    fn resolve_all(&mut self, root: &Path) -> Result<Vec<ResolvedPackage>, ResolveError> {
+        let packages = self.resolve_all_packages(root)?;
+        self.write_lockfile(root, &packages)?;
+        Ok(packages)
+    }
+
     fn bump(
         &mut self,
         ctx: &context::Context,
         root: &Path,
         package: &ResolvedPackage,
         version: &semver::Version,
+        resolver_config: &ResolverConfig,
+        all_packages: &[ResolvedPackage],
     ) -> Result<(), ResolveError> {
         let bumped_version = version.to_string();
         let package_path = root.join(&package.path);
 
+        let go_mod_path = package_path.join("go.mod");
+        let module = if go_mod_path.exists() {
+            let go_mod_str = std::fs::read_to_string(&go_mod_path)?;
+            Some(Self::parse_go_mod(&go_mod_str, &go_mod_path)?.module)
+        } else {
+            None
+        };
+
         if ctx.dry_run {
             log::warn!(
                 "Skip bump for {} to version {} due to dry run",
                 package.name,
                 bumped_version
             );
-            return Ok(());
+        } else {
+            // Update version.go
+            self.update_version_go(&package_path, &bumped_version)?;
+        }
+
+        // Propagate the new version into any other workspace package's
+        // go.mod that requires this module. `all_packages` is the same
+        // workspace membership list `resolve_all` produced, so this reaches
+        // every sibling regardless of how the workspace is enumerated.
+        if let Some(module_path) = &module {
+            let entries: Vec<(String, PathBuf)> = all_packages
+                .iter()
+                .map(|p| (p.name.clone(), p.path.clone()))
+                .collect();
+            self.propagate_bump(
+                ctx,
+                root,
+                &package.name,
+                module_path,
+                &bumped_version,
+                &entries,
+            )?;
         }
 
-        // Update version.go
-        self.update_version_go(&package_path, &bumped_version)?;
+        // Go modules are versioned via git tags rather than a registry
+        // publish step, so unless tagging is deferred to `publish`, create
+        // the tag right away.
+        if resolver_config.tag_at_bump {
+            if ctx.dry_run {
+                log::warn!(
+                    "Skip creating git tag for {} at version {} due to dry run",
+                    package.name,
+                    bumped_version
+                );
+            } else if let Some(module_path) = &module {
+                self.create_git_tag(
+                    root,
+                    &package.path,
+                    module_path,
+                    &bumped_version,
+                    resolver_config,
+                )?;
+            } else {
+                log::warn!(
+                    "Cannot create git tag for {}: no go.mod found",
+                    package.name
+                );
+            }
+        }
 
         Ok(())
     }
@@ -478,65 +988,101 @@ impl Resolver for GoResolver {
         root: &Path,
         packages: &mut Vec<(String, PackageConfig)>,
     ) -> Result<(), ResolveError> {
-        let cached_packages = packages
+        // Indices of Go packages, in their original relative order. Non-Go
+        // packages are left untouched and keep their position.
+        let go_indices: Vec<usize> = packages
             .iter()
-            .filter(|(_, cfg)| cfg.resolver == ResolverType::Go)
-            .try_fold(HashMap::new(), |mut acc, (name, cfg)| {
-                let go_mod_path = root.join(&cfg.path).join("go.mod");
-                let go_mod_str = std::fs::read_to_string(&go_mod_path)?;
-                let go_mod = Self::parse_go_mod(&go_mod_str, &go_mod_path)?;
-                acc.insert(name.clone(), go_mod);
-                Ok::<_, ResolveError>(acc)
-            })?;
+            .enumerate()
+            .filter(|(_, (_, cfg))| cfg.resolver == ResolverType::Go)
+            .map(|(i, _)| i)
+            .collect();
 
-        // Build a map of module path -> package name for dependency resolution
-        let module_to_name: HashMap<String, String> = cached_packages
+        let entries: Vec<(String, PathBuf)> = go_indices
             .iter()
-            .map(|(name, go_mod)| (go_mod.module.clone(), name.clone()))
+            .map(|&i| {
+                let (name, cfg) = &packages[i];
+                (name.clone(), cfg.path.clone())
+            })
             .collect();
 
-        packages.sort_by(|(a, a_cfg), (b, b_cfg)| {
-            if a_cfg.resolver == ResolverType::Go && b_cfg.resolver == ResolverType::Go {
-                let a_mod = cached_packages.get(a).unwrap();
-                let b_mod = cached_packages.get(b).unwrap();
+        let (cached_packages, dependents) = Self::dependency_graph(root, &entries)?;
 
-                // Check if a depends on b
-                let a_depends_on_b = a_mod.require.iter().any(|req| {
-                    module_to_name
-                        .get(&req.path)
-                        .is_some_and(|dep_name| dep_name == b)
-                });
+        // Build a dependency graph: an edge dep -> dependent means `dep` must
+        // be emitted before `dependent`.
+        let names: Vec<String> = entries.iter().map(|(name, _)| name.clone()).collect();
 
-                // Check if b depends on a
-                let b_depends_on_a = b_mod.require.iter().any(|req| {
-                    module_to_name
-                        .get(&req.path)
-                        .is_some_and(|dep_name| dep_name == a)
-                });
+        let mut in_degree: HashMap<String, usize> =
+            names.iter().map(|name| (name.clone(), 0)).collect();
+        for dependent_names in dependents.values() {
+            for dependent in dependent_names {
+                if let Some(degree) = in_degree.get_mut(dependent) {
+                    *degree += 1;
+                }
+            }
+        }
 
-                if a_depends_on_b {
-                    std::cmp::Ordering::Greater
-                } else if b_depends_on_a {
-                    std::cmp::Ordering::Less
-                } else {
-                    std::cmp::Ordering::Equal
+        // Kahn's algorithm: seed the queue with zero-in-degree nodes, in
+        // their original relative order, so the result is stable when there
+        // are no dependency constraints.
+        let mut queue: VecDeque<String> = names
+            .iter()
+            .filter(|name| in_degree[*name] == 0)
+            .cloned()
+            .collect();
+
+        let mut order = Vec::with_capacity(names.len());
+
+        while let Some(name) = queue.pop_front() {
+            order.push(name.clone());
+
+            if let Some(deps) = dependents.get(&name) {
+                for dependent in deps {
+                    let degree = in_degree.get_mut(dependent).unwrap();
+                    *degree -= 1;
+                    if *degree == 0 {
+                        queue.push_back(dependent.clone());
+                    }
                 }
-            } else {
-                std::cmp::Ordering::Equal
             }
-        });
+        }
+
+        if order.len() != names.len() {
+            let cyclic_modules = names
+                .iter()
+                .filter(|name| !order.contains(name))
+                .map(|name| cached_packages[name].module.clone())
+                .collect();
+
+            return Err(ResolveError::DependencyCycle {
+                modules: cyclic_modules,
+            });
+        }
+
+        // Re-assign the sorted Go packages back into their original index
+        // slots, leaving non-Go packages exactly where they were.
+        let mut sorted: HashMap<String, (String, PackageConfig)> = go_indices
+            .iter()
+            .map(|&i| (packages[i].0.clone(), packages[i].clone()))
+            .collect();
+
+        for (&i, name) in go_indices.iter().zip(order.iter()) {
+            packages[i] = sorted.remove(name).unwrap();
+        }
 
         Ok(())
     }
 
     fn publish(
         &mut self,
+        root: &Path,
         package: &ResolvedPackage,
         resolver_config: &ResolverConfig,
         dry_run: bool,
     ) -> Result<(), ResolveError> {
-        // Go modules don't have a traditional publish step,
-        // versioning is done via git tags
+        // Go modules don't have a traditional publish step, versioning is
+        // done via git tags. Tag creation itself happens in `bump` when
+        // `resolver_config.tag_at_bump` is set, and is deferred to here
+        // otherwise.
 
         log::info!("Running prepublish commands for {}", package.name);
         for prepublish in &resolver_config.prepublish {
@@ -568,6 +1114,405 @@ impl Resolver for GoResolver {
             utils::run_command(publish, &package.path)?;
         }
 
+        // Tagging may be deferred from bump time to here.
+        if !resolver_config.tag_at_bump {
+            let new_version = package.version.to_string();
+            if dry_run {
+                log::warn!(
+                    "Skip creating git tag for {} at version {} due to dry run",
+                    package.name,
+                    new_version
+                );
+            } else {
+                let go_mod_path = root.join(&package.path).join("go.mod");
+                let module = if go_mod_path.exists() {
+                    let go_mod_str = std::fs::read_to_string(&go_mod_path)?;
+                    Some(Self::parse_go_mod(&go_mod_str, &go_mod_path)?.module)
+                } else {
+                    None
+                };
+
+                match &module {
+                    Some(module_path) => {
+                        self.create_git_tag(
+                            root,
+                            &package.path,
+                            module_path,
+                            &new_version,
+                            resolver_config,
+                        )?;
+                    }
+                    None => {
+                        log::warn!(
+                            "Cannot create git tag for {}: no go.mod found",
+                            package.name
+                        );
+                    }
+                }
+            }
+        }
+
         Ok(())
     }
+
+    /// Build this resolver's contribution to the workspace lockfile: one
+    /// entry per resolved package, with its version source and the
+    /// intra-workspace packages it depends on. Reuses the same
+    /// `require`/`module_to_name` graph as `sort_packages`, so the lockfile
+    /// and the publish order always agree.
+    fn lock_entries(
+        &mut self,
+        root: &Path,
+        packages: &[ResolvedPackage],
+    ) -> Result<Vec<LockEntry>, ResolveError> {
+        let entries: Vec<(String, PathBuf)> = packages
+            .iter()
+            .map(|package| (package.name.clone(), package.path.clone()))
+            .collect();
+
+        let (cached_packages, dependents) = Self::dependency_graph(root, &entries)?;
+
+        let mut depends_on: HashMap<String, Vec<String>> = HashMap::new();
+        for (dep_name, dependent_names) in &dependents {
+            for dependent in dependent_names {
+                depends_on
+                    .entry(dependent.clone())
+                    .or_default()
+                    .push(dep_name.clone());
+            }
+        }
+
+        packages
+            .iter()
+            .map(|package| {
+                let module = cached_packages.get(&package.name).map(|m| m.module.clone());
+                let source = match &module {
+                    Some(module_path) => self.version_source(root, &package.path, module_path)?,
+                    None => VersionSource::Default,
+                };
+
+                Ok(LockEntry {
+                    name: package.name.clone(),
+                    module,
+                    version: package.version.to_string(),
+                    source,
+                    depends_on: depends_on.remove(&package.name).unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_workspace(label: &str) -> PathBuf {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!(
+            "semifold-go-resolver-test-{}-{}-{}",
+            label,
+            std::process::id(),
+            nanos
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_go_mod(root: &Path, dir: &str, content: &str) {
+        let pkg_dir = root.join(dir);
+        std::fs::create_dir_all(&pkg_dir).unwrap();
+        std::fs::write(pkg_dir.join("go.mod"), content).unwrap();
+    }
+
+    fn go_package_config(path: &str) -> PackageConfig {
+        PackageConfig {
+            path: path.into(),
+            resolver: ResolverType::Go,
+            version_mode: VersionMode::Semantic,
+            assets: vec![],
+        }
+    }
+
+    #[test]
+    fn sort_packages_detects_a_dependency_cycle() {
+        let root = temp_workspace("cycle");
+        write_go_mod(
+            &root,
+            "a",
+            "module example.com/a\n\ngo 1.21\n\nrequire example.com/b v0.0.0\n",
+        );
+        write_go_mod(
+            &root,
+            "b",
+            "module example.com/b\n\ngo 1.21\n\nrequire example.com/a v0.0.0\n",
+        );
+
+        let mut packages = vec![
+            ("a".to_string(), go_package_config("a")),
+            ("b".to_string(), go_package_config("b")),
+        ];
+
+        let result = GoResolver.sort_packages(&root, &mut packages);
+
+        std::fs::remove_dir_all(&root).ok();
+
+        assert!(matches!(result, Err(ResolveError::DependencyCycle { .. })));
+    }
+
+    #[test]
+    fn sort_packages_orders_dependencies_before_dependents() {
+        let root = temp_workspace("order");
+        write_go_mod(&root, "a", "module example.com/a\n\ngo 1.21\n");
+        write_go_mod(
+            &root,
+            "b",
+            "module example.com/b\n\ngo 1.21\n\nrequire example.com/a v0.0.0\n",
+        );
+
+        let mut packages = vec![
+            ("b".to_string(), go_package_config("b")),
+            ("a".to_string(), go_package_config("a")),
+        ];
+
+        GoResolver.sort_packages(&root, &mut packages).unwrap();
+
+        std::fs::remove_dir_all(&root).ok();
+
+        let names: Vec<&str> = packages.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn propagate_bump_rewrites_require_in_dependent_go_mods() {
+        let root = temp_workspace("propagate");
+        write_go_mod(&root, "lib", "module example.com/lib\n\ngo 1.21\n");
+        write_go_mod(
+            &root,
+            "app",
+            "module example.com/app\n\ngo 1.21\n\nrequire example.com/lib v1.0.0\n",
+        );
+
+        let ctx = context::Context::default();
+        let entries = vec![
+            ("lib".to_string(), PathBuf::from("lib")),
+            ("app".to_string(), PathBuf::from("app")),
+        ];
+
+        GoResolver
+            .propagate_bump(&ctx, &root, "lib", "example.com/lib", "1.1.0", &entries)
+            .unwrap();
+
+        let updated = std::fs::read_to_string(root.join("app").join("go.mod")).unwrap();
+
+        std::fs::remove_dir_all(&root).ok();
+
+        assert!(updated.contains("example.com/lib v1.1.0"));
+    }
+
+    #[test]
+    fn propagate_bump_respects_dry_run() {
+        let root = temp_workspace("propagate-dry-run");
+        write_go_mod(&root, "lib", "module example.com/lib\n\ngo 1.21\n");
+        write_go_mod(
+            &root,
+            "app",
+            "module example.com/app\n\ngo 1.21\n\nrequire example.com/lib v1.0.0\n",
+        );
+
+        let ctx = context::Context { dry_run: true };
+        let entries = vec![
+            ("lib".to_string(), PathBuf::from("lib")),
+            ("app".to_string(), PathBuf::from("app")),
+        ];
+
+        GoResolver
+            .propagate_bump(&ctx, &root, "lib", "example.com/lib", "1.1.0", &entries)
+            .unwrap();
+
+        let content = std::fs::read_to_string(root.join("app").join("go.mod")).unwrap();
+
+        std::fs::remove_dir_all(&root).ok();
+
+        assert!(content.contains("v1.0.0"));
+    }
+
+    fn init_git_repo(root: &Path) {
+        Command::new("git").args(["init"]).current_dir(root).output().unwrap();
+        Command::new("git")
+            .args(["config", "user.email", "test@example.com"])
+            .current_dir(root)
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["config", "user.name", "test"])
+            .current_dir(root)
+            .output()
+            .unwrap();
+        std::fs::write(root.join("README"), "placeholder\n").unwrap();
+        Command::new("git").args(["add", "-A"]).current_dir(root).output().unwrap();
+        Command::new("git")
+            .args(["commit", "-m", "initial"])
+            .current_dir(root)
+            .output()
+            .unwrap();
+    }
+
+    #[test]
+    fn create_git_tag_round_trips_for_a_submodule_whose_import_path_differs_from_its_directory() {
+        let root = temp_workspace("submodule-tag");
+        init_git_repo(&root);
+
+        let package_path = Path::new("sub");
+        let module_path = "example.com/org/repo/sub";
+        let resolver_config = ResolverConfig::default();
+
+        GoResolver
+            .create_git_tag(&root, package_path, module_path, "1.2.0", &resolver_config)
+            .unwrap();
+
+        let found = GoResolver
+            .extract_version_from_git_tag(&root, module_path)
+            .unwrap();
+
+        std::fs::remove_dir_all(&root).ok();
+
+        assert_eq!(found, Some("1.2.0".to_string()));
+    }
+
+    #[test]
+    fn resolve_dependency_name_follows_local_replace_directive() {
+        let go_mod = GoMod {
+            module: "example.com/app".to_string(),
+            go_version: None,
+            require: vec![GoRequire {
+                path: "example.com/forked-lib".to_string(),
+                version: "v1.0.0".to_string(),
+            }],
+            replace: vec![GoReplace {
+                old_path: "example.com/forked-lib".to_string(),
+                new_path: "../lib".to_string(),
+            }],
+        };
+
+        let root = Path::new("/workspace");
+        let package_dir = Path::new("app");
+
+        let module_to_name: HashMap<String, String> = HashMap::new();
+        let mut path_to_name = HashMap::new();
+        path_to_name.insert(GoResolver::normalize_path(&root.join("lib")), "lib".to_string());
+
+        let resolved = GoResolver::resolve_dependency_name(
+            "example.com/forked-lib",
+            &go_mod,
+            package_dir,
+            root,
+            &module_to_name,
+            &path_to_name,
+        );
+
+        assert_eq!(resolved, Some("lib".to_string()));
+    }
+
+    #[test]
+    fn resolve_dependency_name_prefers_replace_over_require_when_both_match() {
+        // `require example.com/lib` matches the workspace package "lib-by-name"
+        // via module_to_name, but a `replace` also redirects that same
+        // require path to a different local directory. Go's own resolution
+        // follows the replace, so the dependency edge must point at the
+        // replace target, not the require-path match.
+        let go_mod = GoMod {
+            module: "example.com/app".to_string(),
+            go_version: None,
+            require: vec![GoRequire {
+                path: "example.com/lib".to_string(),
+                version: "v1.0.0".to_string(),
+            }],
+            replace: vec![GoReplace {
+                old_path: "example.com/lib".to_string(),
+                new_path: "../lib-fork".to_string(),
+            }],
+        };
+
+        let root = Path::new("/workspace");
+        let package_dir = Path::new("app");
+
+        let mut module_to_name = HashMap::new();
+        module_to_name.insert("example.com/lib".to_string(), "lib-by-name".to_string());
+
+        let mut path_to_name = HashMap::new();
+        path_to_name.insert(
+            GoResolver::normalize_path(&root.join("lib-fork")),
+            "lib-fork".to_string(),
+        );
+
+        let resolved = GoResolver::resolve_dependency_name(
+            "example.com/lib",
+            &go_mod,
+            package_dir,
+            root,
+            &module_to_name,
+            &path_to_name,
+        );
+
+        assert_eq!(resolved, Some("lib-fork".to_string()));
+    }
+
+    #[test]
+    fn resolve_all_preserves_other_backends_lockfile_entries() {
+        let root = temp_workspace("lockfile-merge");
+        write_go_mod(&root, ".", "module example.com/app\n\ngo 1.21\n");
+
+        // Simulate a previous lockfile containing an entry from a different
+        // resolver backend (e.g. npm), which this Go-only resolve_all run
+        // never touches.
+        let previous = lockfile::Lockfile::new(vec![LockEntry {
+            name: "frontend".to_string(),
+            module: None,
+            version: "2.0.0".to_string(),
+            source: VersionSource::Default,
+            depends_on: vec![],
+        }]);
+        previous.write(&root).unwrap();
+
+        GoResolver.resolve_all(&root).unwrap();
+
+        let written = lockfile::Lockfile::read(&root).unwrap().unwrap();
+
+        std::fs::remove_dir_all(&root).ok();
+
+        let names: Vec<&str> = written
+            .packages
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect();
+        assert!(names.contains(&"frontend"));
+        assert!(names.contains(&"app"));
+    }
+
+    #[test]
+    fn rewrite_require_version_skips_downgrading_a_newer_requirement() {
+        let content = "module example.com/app\n\nrequire example.com/lib v1.5.0\n";
+
+        let (updated, changed) =
+            GoResolver::rewrite_require_version(content, "example.com/lib", "1.2.0");
+
+        assert!(!changed);
+        assert!(updated.contains("v1.5.0"));
+    }
+
+    #[test]
+    fn rewrite_require_version_orders_pseudo_versions_by_semver_precedence() {
+        let content =
+            "module example.com/app\n\nrequire example.com/lib v0.0.0-20191109021931-daa7c04131f5\n";
+
+        let (updated, changed) =
+            GoResolver::rewrite_require_version(content, "example.com/lib", "1.0.0");
+
+        assert!(changed);
+        assert!(updated.contains("v1.0.0"));
+    }
 }
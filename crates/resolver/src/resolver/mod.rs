@@ -0,0 +1,81 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    config::{PackageConfig, ResolverConfig},
+    context::Context,
+    error::ResolveError,
+    lockfile::LockEntry,
+};
+
+pub mod go;
+
+/// Which `Resolver` backend handles a package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolverType {
+    Go,
+    Npm,
+    Cargo,
+}
+
+/// A package as resolved by one of the `Resolver` backends: its name,
+/// current version, workspace-relative path, and whether it should be
+/// excluded from publishing.
+#[derive(Debug, Clone)]
+pub struct ResolvedPackage {
+    pub name: String,
+    pub version: semver::Version,
+    pub path: PathBuf,
+    pub private: bool,
+}
+
+/// An ecosystem-specific backend (Go modules, npm, Cargo, ...) that knows
+/// how to resolve, order, version-bump, and publish the packages it owns.
+pub trait Resolver {
+    /// Resolve a single package's current version and metadata.
+    fn resolve(
+        &mut self,
+        root: &Path,
+        pkg_config: &PackageConfig,
+    ) -> Result<ResolvedPackage, ResolveError>;
+
+    /// Discover and resolve every package this backend owns under `root`.
+    fn resolve_all(&mut self, root: &Path) -> Result<Vec<ResolvedPackage>, ResolveError>;
+
+    /// Bump `package` to `version`, propagating the change to any other
+    /// workspace package that depends on it.
+    fn bump(
+        &mut self,
+        ctx: &Context,
+        root: &Path,
+        package: &ResolvedPackage,
+        version: &semver::Version,
+        resolver_config: &ResolverConfig,
+        all_packages: &[ResolvedPackage],
+    ) -> Result<(), ResolveError>;
+
+    /// Reorder `packages` in place so dependencies are published before
+    /// their dependents.
+    fn sort_packages(
+        &mut self,
+        root: &Path,
+        packages: &mut Vec<(String, PackageConfig)>,
+    ) -> Result<(), ResolveError>;
+
+    /// Publish `package`, running any configured prepublish/publish commands.
+    fn publish(
+        &mut self,
+        root: &Path,
+        package: &ResolvedPackage,
+        resolver_config: &ResolverConfig,
+        dry_run: bool,
+    ) -> Result<(), ResolveError>;
+
+    /// Build this backend's contribution to the workspace lockfile: one
+    /// entry per resolved package, with its version source and the
+    /// intra-workspace packages it depends on.
+    fn lock_entries(
+        &mut self,
+        root: &Path,
+        packages: &[ResolvedPackage],
+    ) -> Result<Vec<LockEntry>, ResolveError>;
+}
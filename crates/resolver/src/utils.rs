@@ -0,0 +1,24 @@
+use std::path::Path;
+use std::process::Command;
+
+use crate::{config::CommandSpec, error::ResolveError};
+
+/// Run a user-configured prepublish/publish command with its working
+/// directory set to the package path.
+pub fn run_command(spec: &CommandSpec, package_path: &Path) -> Result<(), ResolveError> {
+    let args = spec.args.clone().unwrap_or_default();
+
+    let output = Command::new(&spec.command)
+        .args(&args)
+        .current_dir(package_path)
+        .output()?;
+
+    if !output.status.success() {
+        return Err(ResolveError::CommandFailed {
+            command: format!("{} {}", spec.command, args.join(" ")),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        });
+    }
+
+    Ok(())
+}